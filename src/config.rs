@@ -0,0 +1,67 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// `$XDG_CONFIG_HOME/pctrl`, falling back to `~/.config/pctrl`.
+fn config_dir() -> PathBuf {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+            home.join(".config")
+        }
+    };
+    base.join("pctrl")
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    /// Fraction a single `Inc`/`Dec` nudges the volume by
+    pub(crate) volume_step: f64,
+    /// Regex patterns matched against a device's (lowercased) name
+    pub(crate) ignore: Vec<String>,
+    /// Ordered name/description substrings, earlier wins, unmatched sorts last
+    pub(crate) priority: Vec<String>,
+    /// Fraction that increase operations are clamped to
+    pub(crate) max_volume: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            volume_step: 0.05,
+            ignore: vec!["monitor".to_string()],
+            priority: Vec::new(),
+            max_volume: 1.0,
+        }
+    }
+}
+
+impl Config {
+    /// Loads `config.toml` from the XDG config dir, falling back to defaults
+    pub(crate) fn load() -> anyhow::Result<Config> {
+        let path = config_dir().join("config.toml");
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub(crate) fn ignore_regexes(&self) -> anyhow::Result<Vec<Regex>> {
+        self.ignore
+            .iter()
+            .map(|pattern| Regex::new(pattern).map_err(anyhow::Error::from))
+            .collect()
+    }
+
+    /// Index of the first matching `priority` pattern, or `priority.len()` if none. Lower wins.
+    pub(crate) fn priority_rank(&self, name: &str, description: &str) -> usize {
+        let haystack = format!("{} {}", name, description).to_lowercase();
+        self.priority
+            .iter()
+            .position(|pattern| haystack.contains(&pattern.to_lowercase()))
+            .unwrap_or(self.priority.len())
+    }
+}
@@ -1,40 +1,72 @@
-use std::fs::{File, OpenOptions};
-use std::io::{self, Read, Write};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::PathBuf;
 use crate::InputOutput;
 use anyhow;
 
-const INPUT_STATE: &str = "/tmp/pctrl-input";
-const OUTPUT_STATE: &str = "/tmp/pctrl-output";
+const INPUT_STATE_FILE: &str = "input";
+const OUTPUT_STATE_FILE: &str = "output";
 
-pub (crate) fn read_device_index(input_output: InputOutput) -> anyhow::Result<Option<u32>>{
-    let file_path = match input_output{
-        InputOutput::Input => INPUT_STATE,
-        InputOutput::Output => OUTPUT_STATE,
+/// `$XDG_STATE_HOME/pctrl`, falling back to `~/.local/state/pctrl`, created
+/// on demand so the selected device survives reboots instead of living in
+/// `/tmp`.
+fn state_dir() -> anyhow::Result<PathBuf> {
+    let base = match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = std::env::var_os("HOME").ok_or_else(|| anyhow::anyhow!("HOME not set"))?;
+            PathBuf::from(home).join(".local").join("state")
+        }
     };
+    let dir = base.join("pctrl");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn state_file_path(input_output: InputOutput) -> anyhow::Result<PathBuf> {
+    let file_name = match input_output {
+        InputOutput::Input => INPUT_STATE_FILE,
+        InputOutput::Output => OUTPUT_STATE_FILE,
+    };
+    Ok(state_dir()?.join(file_name))
+}
+
+pub(crate) enum StoredDevice {
+    Name(String),
+    LegacyIndex(u32),
+}
+
+/// Reads the stable device name persisted from a previous run. Old state
+/// files (pre-XDG-migration) stored a numeric PulseAudio index instead; when
+/// the content parses as a plain `u32` it's returned as a legacy index so the
+/// caller can resolve it once and re-persist it as a name.
+pub(crate) fn read_device_name(input_output: InputOutput) -> anyhow::Result<Option<StoredDevice>> {
+    let file_path = state_file_path(input_output)?;
 
-    // Try to open the file with read and write permissions, creating it if it doesn't exist
     let mut file = OpenOptions::new()
         .read(true)
         .write(true)
         .create(true)
         .open(file_path)?;
 
-    // Read the file content
     let mut content = String::new();
     file.read_to_string(&mut content)?;
+    let content = content.trim();
 
-    Ok(content.parse::<u32>().ok())
-}
+    if content.is_empty() {
+        return Ok(None);
+    }
 
-pub (crate) fn write_device_index(input_output: InputOutput, index: u32) -> anyhow::Result<()> {
-    let file_path = match input_output{
-        InputOutput::Input => INPUT_STATE,
-        InputOutput::Output => OUTPUT_STATE,
-    };
-    // Try to open the file with read and write permissions, creating it if it doesn't exist
-    let mut file = File::create(file_path)?;
+    if let Ok(idx) = content.parse::<u32>() {
+        return Ok(Some(StoredDevice::LegacyIndex(idx)));
+    }
 
-    file.write_all(index.to_string().as_bytes())?;
+    Ok(Some(StoredDevice::Name(content.to_string())))
+}
 
+pub(crate) fn write_device_name(input_output: InputOutput, name: &str) -> anyhow::Result<()> {
+    let file_path = state_file_path(input_output)?;
+    let mut file = File::create(file_path)?;
+    file.write_all(name.as_bytes())?;
     Ok(())
 }
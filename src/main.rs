@@ -6,10 +6,24 @@ use pulsectl::controllers::{
     types::{ApplicationInfo, DeviceInfo},
     AppControl, DeviceControl, SinkController, SourceController,
 };
+use pulsectl::pulse::volume::Volume;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use std::time::Duration;
 
 use clap::{Parser, ValueEnum};
+mod config;
 mod fs_helpers;
 
+use config::Config;
+
+/// Consecutive polls a device's presence/absence must persist before watch_loop acts on it
+const DEBOUNCE_TICKS: u32 = 2;
+
+const DEFAULT_POLL_MS: u64 = 500;
+
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
@@ -27,38 +41,110 @@ struct Cli {
 
     #[arg(long)]
     prev: Option<u32>,
+
+    /// Stay running and watch for device changes instead of exiting after one action
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    watch: bool,
+
+    /// Poll interval in milliseconds used by `--watch`.
+    #[arg(long, default_value_t = DEFAULT_POLL_MS)]
+    poll_ms: u64,
+
+    /// Overrides `volume_step` from the config file.
+    #[arg(long)]
+    volume_step: Option<f64>,
+
+    /// Stream index or substring name match for the `App*` actions
+    #[arg(long)]
+    app: Option<String>,
+
+    /// Target device index for `Action::AppMove`.
+    #[arg(long)]
+    device: Option<u32>,
+
+    /// Output format for `--status`; under `--watch` also selects streaming and defaults status to `all`
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Target volume as a percentage (e.g. `75`) for `Action::Set`.
+    #[arg(long)]
+    value: Option<u32>,
+
+    /// Overrides `max_volume` from the config file
+    #[arg(long)]
+    max_volume: Option<f64>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Looks up a device by its stable name rather than its (unstable) numeric index
+fn get_device_by_name(
+    controller: &mut Box<Controller>,
+    name: &str,
+) -> anyhow::Result<Option<DeviceInfo>> {
+    Ok(controller
+        .list_devices()?
+        .into_iter()
+        .find(|d| d.name.as_deref() == Some(name)))
+}
+
+fn first_available_device(
+    controller: &mut Box<Controller>,
+    ignore: &[Regex],
+) -> anyhow::Result<DeviceInfo> {
+    controller
+        .list_devices()?
+        .iter()
+        .filter(|d| ignore_monitor_devs(d, ignore))
+        .cloned()
+        .next()
+        .ok_or(anyhow!("No devices found"))
 }
 
 fn get_default_device(
     controller: &mut Box<Controller>,
     input_output: InputOutput,
+    config: &Config,
 ) -> anyhow::Result<DeviceInfo> {
-    if let Some(idx) = fs_helpers::read_device_index(input_output)?{
-        if let Ok(device) = controller.get_device_by_index(idx) {
-            debug!("Device with index #{} found: {:?}", idx, device.name);
-            Ok(device)
-        }else{
-            warn!("Device with index {} not found - figuring out new default device", idx);
-            //TODO: try to fetch default device first from pulse audio
+    let ignore = config.ignore_regexes()?;
+    match fs_helpers::read_device_name(input_output)? {
+        Some(fs_helpers::StoredDevice::Name(name)) => {
+            if let Some(device) = get_device_by_name(controller, &name)? {
+                debug!("Device {:?} found", device.name);
+                Ok(device)
+            } else {
+                warn!("Device {:?} not found - figuring out new default device", name);
+                let dev = first_available_device(controller, &ignore)?;
+                fs_helpers::write_device_name(input_output, &dev.name.clone().unwrap_or_default())?;
+                Ok(dev)
+            }
+        }
+        Some(fs_helpers::StoredDevice::LegacyIndex(idx)) => {
+            if let Ok(device) = controller.get_device_by_index(idx) {
+                info!("Migrating legacy index-based state to device name {:?}", device.name);
+                fs_helpers::write_device_name(input_output, &device.name.clone().unwrap_or_default())?;
+                Ok(device)
+            } else {
+                warn!("Legacy index {} not found - figuring out new default device", idx);
+                let dev = first_available_device(controller, &ignore)?;
+                fs_helpers::write_device_name(input_output, &dev.name.clone().unwrap_or_default())?;
+                Ok(dev)
+            }
+        }
+        None => {
+            debug!("No previous state stored");
             let dev = controller
                 .list_devices()?
-                .iter()
-                .filter(ignore_monitor_devs)
+                .first()
                 .cloned()
-                .next()
                 .ok_or(anyhow!("No devices found"))?;
-            fs_helpers::write_device_index(input_output, dev.index)?;
-            Ok(dev.clone())
-        }
-    }else{
-        debug!("No previous state stored");
-        let dev = controller
-            .list_devices()?
-            .first()
-            .cloned()
-            .ok_or(anyhow!("No devices found"))?;
-        fs_helpers::write_device_index(input_output, dev.index)?;
-        Ok(dev.clone())
+            fs_helpers::write_device_name(input_output, &dev.name.clone().unwrap_or_default())?;
+            Ok(dev)
+        }
     }
 }
 
@@ -67,7 +153,7 @@ enum Direction {
     Backward,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum InputOutput {
     Input,
     Output,
@@ -80,6 +166,18 @@ enum Action {
     Mute,
     Inc,
     Dec,
+    /// Sets the volume to the absolute percentage given by `--value`.
+    Set,
+    /// Lists every application playing on/recording from this target.
+    AppList,
+    /// Toggles mute on the application selected with `--app`.
+    AppMute,
+    /// Raises the volume of the application selected with `--app`.
+    AppInc,
+    /// Lowers the volume of the application selected with `--app`.
+    AppDec,
+    /// Moves the application selected with `--app` to `--device`.
+    AppMove,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -88,6 +186,41 @@ enum Status {
     Volume,
     Name,
     Desc,
+    /// Bundles all four fields above into one `DeviceStatus`.
+    All,
+}
+
+/// The four status fields bundled together for `Status::All`/`--format json`
+#[derive(Serialize)]
+struct DeviceStatus {
+    name: String,
+    desc: String,
+    volume: String,
+    muted: bool,
+}
+
+impl DeviceStatus {
+    fn from_device(info: &DeviceInfo) -> Self {
+        DeviceStatus {
+            name: info.name.clone().unwrap_or_default(),
+            desc: info.description.clone().unwrap_or_default(),
+            volume: info.volume.to_string(),
+            muted: info.mute,
+        }
+    }
+}
+
+/// Renders one `--status` reading as a single scalar or the bundled `DeviceStatus` JSON
+fn format_status(status: Status, format: Format, info: &DeviceInfo) -> anyhow::Result<String> {
+    Ok(match (status, format) {
+        (Status::All, _) | (_, Format::Json) => {
+            serde_json::to_string(&DeviceStatus::from_device(info))?
+        }
+        (Status::Muted, Format::Text) => info.mute.to_string(),
+        (Status::Volume, Format::Text) => info.volume.to_string(),
+        (Status::Name, Format::Text) => info.name.clone().unwrap_or_default(),
+        (Status::Desc, Format::Text) => info.description.clone().unwrap_or_default(),
+    })
 }
 
 
@@ -118,12 +251,59 @@ where
     }
 }
 
-fn ignore_monitor_devs(d: &&DeviceInfo) -> bool {
-    !d.name
-        .clone()
-        .unwrap_or_default()
-        .to_lowercase()
-        .contains("monitor")
+fn ignore_monitor_devs(d: &&DeviceInfo, ignore: &[Regex]) -> bool {
+    let name = d.name.clone().unwrap_or_default().to_lowercase();
+    !ignore.iter().any(|re| re.is_match(&name))
+}
+
+/// Current volume as a fraction of PulseAudio's nominal `Volume::NORMAL` (`1.0` == 100%).
+fn volume_fraction(info: &DeviceInfo) -> f64 {
+    info.volume.avg().0 as f64 / Volume::NORMAL.0 as f64
+}
+
+/// Clamps `index`'s volume down to `max_volume` if it's currently above that ceiling
+fn clamp_device_volume(
+    controller: &mut Box<Controller>,
+    index: u32,
+    max_volume: f64,
+) -> anyhow::Result<DeviceInfo> {
+    let info = controller.get_device_by_index(index)?;
+    if volume_fraction(&info) > max_volume {
+        controller.set_device_volume_by_percent(index, max_volume);
+        controller.get_device_by_index(index).map_err(Into::into)
+    } else {
+        Ok(info)
+    }
+}
+
+/// Resolves `--app` as a stream index, falling back to a substring name match
+fn resolve_app(controller: &mut Box<Controller>, selector: &str) -> anyhow::Result<ApplicationInfo> {
+    if let Ok(idx) = selector.parse::<u32>() {
+        if let Ok(app) = controller.get_app_by_index(idx) {
+            return Ok(app);
+        }
+    }
+
+    let needle = selector.to_lowercase();
+    controller
+        .list_applications()?
+        .into_iter()
+        .find(|app| app.name.clone().unwrap_or_default().to_lowercase().contains(&needle))
+        .ok_or(anyhow!("No application matching {:?} found", selector))
+}
+
+fn list_apps(controller: &mut Box<Controller>) -> anyhow::Result<()> {
+    for app in controller.list_applications()? {
+        println!(
+            "{}\t{}\tdevice={:?}\tvolume={}\tmuted={}",
+            app.index,
+            app.name.clone().unwrap_or_default(),
+            app.sink_index,
+            app.volume,
+            app.mute,
+        );
+    }
+    Ok(())
 }
 
 fn next_dev(
@@ -131,8 +311,16 @@ fn next_dev(
     direction: Direction,
     prev: DeviceInfo,
     input_output: InputOutput,
+    config: &Config,
 ) -> anyhow::Result<()> {
-    let devices = controller.list_devices().unwrap_or_default();
+    let ignore = config.ignore_regexes()?;
+    let mut devices = controller.list_devices().unwrap_or_default();
+    devices.sort_by_key(|d| {
+        config.priority_rank(
+            d.name.as_deref().unwrap_or_default(),
+            d.description.as_deref().unwrap_or_default(),
+        )
+    });
 
     let iter: Either<_,_> = match direction {
         Direction::Forward => Either::Left(devices.iter()),
@@ -140,7 +328,7 @@ fn next_dev(
     };
 
     for d in devices.iter()
-        .filter(ignore_monitor_devs)
+        .filter(|d| ignore_monitor_devs(d, &ignore))
     {
         debug!("Found devices: {:?}", d.index);
     }
@@ -150,17 +338,205 @@ fn next_dev(
         .take(devices.len()*2)
         .skip_while(|d| d.index != prev.index)
         .skip(1)
-        .filter(ignore_monitor_devs)
+        .filter(|d| ignore_monitor_devs(d, &ignore))
         .next()
         .expect("At least one device should be available at this point");
 
     info!("Setting default device to: {:?}", next_device.index);
     controller.set_default(next_device.index)?;
     controller.set_default_device(next_device.name.clone().unwrap_or_default().as_ref())?;
-    fs_helpers::write_device_index(input_output, next_device.index)?;
+    fs_helpers::write_device_name(input_output, &next_device.name.clone().unwrap_or_default())?;
     Ok(())
 }
 
+#[derive(Debug)]
+enum Event {
+    DeviceAdded(DeviceInfo),
+    DeviceRemoved(DeviceInfo),
+    DefaultChanged(DeviceInfo),
+}
+
+/// Picks the highest-priority remaining device once `missing` is gone
+fn pick_fallback_device(
+    devices: &[DeviceInfo],
+    missing: &DeviceInfo,
+    ignore: &[Regex],
+    config: &Config,
+) -> Option<DeviceInfo> {
+    let mut candidates: Vec<&DeviceInfo> = devices
+        .iter()
+        .filter(|d| ignore_monitor_devs(d, ignore) && d.index != missing.index)
+        .collect();
+    candidates.sort_by_key(|d| rank(config, d));
+    candidates.into_iter().next().cloned()
+}
+
+fn rank(config: &Config, d: &DeviceInfo) -> usize {
+    config.priority_rank(
+        d.name.as_deref().unwrap_or_default(),
+        d.description.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Returns names missing `DEBOUNCE_TICKS` ticks in a row; kept pulsectl-free to stay testable
+fn tick_missing_names(
+    seen_names: &HashSet<String>,
+    now_names: &HashSet<String>,
+    missing_ticks: &mut HashMap<String, u32>,
+) -> Vec<String> {
+    for name in now_names {
+        missing_ticks.remove(name);
+    }
+
+    let mut expired = Vec::new();
+    for name in seen_names {
+        if !now_names.contains(name) {
+            let ticks = missing_ticks.entry(name.clone()).or_insert(0);
+            *ticks += 1;
+            if *ticks >= DEBOUNCE_TICKS {
+                expired.push(name.clone());
+            }
+        }
+    }
+    expired
+}
+
+/// Returns names present `DEBOUNCE_TICKS` ticks in a row; mirrors `tick_missing_names`
+fn tick_present_names(
+    now_names: &HashSet<String>,
+    present_ticks: &mut HashMap<String, u32>,
+) -> Vec<String> {
+    present_ticks.retain(|name, _| now_names.contains(name));
+
+    let mut debounced = Vec::new();
+    for name in now_names {
+        let ticks = present_ticks.entry(name.clone()).or_insert(0);
+        *ticks += 1;
+        if *ticks == DEBOUNCE_TICKS {
+            debounced.push(name.clone());
+        }
+    }
+    debounced
+}
+
+/// Polls `list_devices()` and diffs against the previously seen set, keyed by name
+fn watch_loop(
+    controller: &mut Box<Controller>,
+    input_output: InputOutput,
+    poll_ms: u64,
+    config: &Config,
+    status: Option<Status>,
+    format: Format,
+) -> anyhow::Result<()> {
+    let ignore = config.ignore_regexes()?;
+    let mut current = get_default_device(controller, input_output, config)?;
+    let mut seen: HashMap<String, DeviceInfo> = controller
+        .list_devices()?
+        .into_iter()
+        .map(|d| (d.name.clone().unwrap_or_default(), d))
+        .collect();
+    let mut missing_ticks: HashMap<String, u32> = HashMap::new();
+    let mut present_ticks: HashMap<String, u32> = HashMap::new();
+    let mut last_reported: Option<String> = None;
+    if let Some(status) = status {
+        let rendered = format_status(status, format, &current)?;
+        println!("{}", rendered);
+        last_reported = Some(rendered);
+    }
+
+    info!("Watching {:?} devices every {}ms", input_output, poll_ms);
+
+    loop {
+        thread::sleep(Duration::from_millis(poll_ms));
+
+        let devices = controller.list_devices()?;
+        let now: HashMap<String, DeviceInfo> = devices
+            .iter()
+            .cloned()
+            .map(|d| (d.name.clone().unwrap_or_default(), d))
+            .collect();
+
+        if let Some(status) = status {
+            if let Some(dev) = now.get(&current.name.clone().unwrap_or_default()) {
+                let rendered = format_status(status, format, dev)?;
+                if last_reported.as_deref() != Some(rendered.as_str()) {
+                    println!("{}", rendered);
+                    last_reported = Some(rendered);
+                }
+            }
+        }
+
+        for (name, dev) in now.iter() {
+            if !seen.contains_key(name) {
+                debug!("Event: {:?}", Event::DeviceAdded(dev.clone()));
+            }
+        }
+
+        let now_names: HashSet<String> = now.keys().cloned().collect();
+        let debounced_present = tick_present_names(&now_names, &mut present_ticks);
+
+        for name in &debounced_present {
+            let dev = match now.get(name) {
+                Some(dev) => dev.clone(),
+                None => continue,
+            };
+
+            if ignore_monitor_devs(&dev, &ignore) && rank(config, &dev) < rank(config, &current) {
+                info!(
+                    "Higher-priority device {:?} present for {} consecutive polls, switching to it",
+                    dev.name, DEBOUNCE_TICKS
+                );
+                controller.set_default(dev.index)?;
+                controller.set_default_device(dev.name.clone().unwrap_or_default().as_str())?;
+                fs_helpers::write_device_name(input_output, &dev.name.clone().unwrap_or_default())?;
+                debug!("Event: {:?}", Event::DefaultChanged(dev.clone()));
+                current = dev;
+            }
+        }
+
+        let seen_names: HashSet<String> = seen.keys().cloned().collect();
+        let expired = tick_missing_names(&seen_names, &now_names, &mut missing_ticks);
+
+        for name in &expired {
+            missing_ticks.remove(name);
+            let dev = match seen.get(name) {
+                Some(dev) => dev.clone(),
+                None => continue,
+            };
+            debug!("Event: {:?}", Event::DeviceRemoved(dev.clone()));
+
+            if dev.index == current.index {
+                if let Some(fallback) = pick_fallback_device(&devices, &current, &ignore, config) {
+                    info!(
+                        "Default device {:?} gone, promoting {:?}",
+                        current.name, fallback.name
+                    );
+                    controller.set_default(fallback.index)?;
+                    controller.set_default_device(
+                        fallback.name.clone().unwrap_or_default().as_str(),
+                    )?;
+                    fs_helpers::write_device_name(
+                        input_output,
+                        &fallback.name.clone().unwrap_or_default(),
+                    )?;
+                    debug!("Event: {:?}", Event::DefaultChanged(fallback.clone()));
+                    current = fallback;
+                } else {
+                    warn!("Default device {:?} gone and no fallback found", dev.name);
+                }
+            }
+        }
+
+        let mut next_seen = now.clone();
+        for (name, dev) in seen.iter() {
+            if !now.contains_key(name) && missing_ticks.contains_key(name) {
+                next_seen.insert(name.clone(), dev.clone());
+            }
+        }
+        seen = next_seen;
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
@@ -173,6 +549,14 @@ fn main() -> anyhow::Result<()> {
 
     builder.filter(None, level).init();
 
+    let mut config = Config::load()?;
+    if let Some(volume_step) = cli.volume_step {
+        config.volume_step = volume_step;
+    }
+    if let Some(max_volume) = cli.max_volume {
+        config.max_volume = max_volume / 100.0;
+    }
+
     let mut controller: Box<Controller> = match cli.target {
         InputOutput::Input => Box::new(SourceController::create()?),
         InputOutput::Output => Box::new(SinkController::create()?),
@@ -188,37 +572,146 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    let prev_device = get_default_device(&mut controller, cli.target)?;
+    if cli.watch {
+        let status = cli.status.or(cli.format.map(|_| Status::All));
+        return watch_loop(
+            &mut controller,
+            cli.target,
+            cli.poll_ms,
+            &config,
+            status,
+            cli.format.unwrap_or(Format::Text),
+        );
+    }
+
+    let prev_device = get_default_device(&mut controller, cli.target, &config)?;
 
     match cli.action {
         Some(Action::Next) => {
-            next_dev(& mut controller, Direction::Forward, prev_device, cli.target)?;
+            next_dev(& mut controller, Direction::Forward, prev_device, cli.target, &config)?;
         }
         Some(Action::Prev) => {
-            next_dev(& mut controller, Direction::Backward, prev_device, cli.target)?;
+            next_dev(& mut controller, Direction::Backward, prev_device, cli.target, &config)?;
         }
         Some(Action::Mute) => {
             controller.set_device_mute_by_index(prev_device.index, !prev_device.mute);
         }
         Some(Action::Inc) => {
-            controller.increase_device_volume_by_percent(prev_device.index, 0.05);
+            controller.increase_device_volume_by_percent(prev_device.index, config.volume_step);
+            let achieved = clamp_device_volume(&mut controller, prev_device.index, config.max_volume)?;
+            info!("Volume now at {}", achieved.volume);
         }
         Some(Action::Dec) => {
-            controller.decrease_device_volume_by_percent(prev_device.index, 0.05);
+            controller.decrease_device_volume_by_percent(prev_device.index, config.volume_step);
+        }
+        Some(Action::Set) => {
+            let value = cli.value.ok_or(anyhow!("--value is required"))?;
+            let target = (value as f64 / 100.0).min(config.max_volume);
+            controller.set_device_volume_by_percent(prev_device.index, target);
+            let achieved = controller.get_device_by_index(prev_device.index)?;
+            info!("Volume set to {} (requested {}%)", achieved.volume, value);
+        }
+        Some(Action::AppList) => {
+            list_apps(&mut controller)?;
+        }
+        Some(Action::AppMute) => {
+            let app = resolve_app(&mut controller, cli.app.as_deref().ok_or(anyhow!("--app is required"))?)?;
+            controller.set_app_mute(app.index, !app.mute)?;
+        }
+        Some(Action::AppInc) => {
+            let app = resolve_app(&mut controller, cli.app.as_deref().ok_or(anyhow!("--app is required"))?)?;
+            controller.increase_app_volume_by_percent(app.index, config.volume_step);
+        }
+        Some(Action::AppDec) => {
+            let app = resolve_app(&mut controller, cli.app.as_deref().ok_or(anyhow!("--app is required"))?)?;
+            controller.decrease_app_volume_by_percent(app.index, config.volume_step);
+        }
+        Some(Action::AppMove) => {
+            let app = resolve_app(&mut controller, cli.app.as_deref().ok_or(anyhow!("--app is required"))?)?;
+            let device = cli.device.ok_or(anyhow!("--device is required"))?;
+            controller.move_app_by_index(app.index, device)?;
         }
         None => {}
     };
 
     if let Some(status) = cli.status{
-        let dev = fs_helpers::read_device_index(cli.target)?;
-        let info = controller.get_device_by_index(dev.unwrap())?;
-        match status{
-            Status::Muted => print!("{}", info.mute),
-            Status::Volume => print!("{}", info.volume),
-            Status::Name => print!("{}", info.name.clone().unwrap_or_default()),
-            Status::Desc => print!("{}", info.description.clone().unwrap_or_default()),
-        }
+        let info = get_default_device(&mut controller, cli.target, &config)?;
+        print!("{}", format_status(status, cli.format.unwrap_or(Format::Text), &info)?);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promotes_fallback_after_two_consecutive_missing_polls() {
+        let mut missing_ticks = HashMap::new();
+        let seen: HashSet<String> = ["default".to_string()].into_iter().collect();
+        let now: HashSet<String> = HashSet::new();
+
+        let expired = tick_missing_names(&seen, &now, &mut missing_ticks);
+        assert!(
+            expired.is_empty(),
+            "a single missed poll should not yet trigger fallback promotion"
+        );
+
+        let expired = tick_missing_names(&seen, &now, &mut missing_ticks);
+        assert_eq!(
+            expired,
+            vec!["default".to_string()],
+            "the default should expire (triggering fallback promotion) once it has been \
+             missing for DEBOUNCE_TICKS consecutive polls"
+        );
+    }
+
+    #[test]
+    fn resets_debounce_when_device_reappears() {
+        let mut missing_ticks = HashMap::new();
+        let seen: HashSet<String> = ["default".to_string()].into_iter().collect();
+        let absent: HashSet<String> = HashSet::new();
+        let present: HashSet<String> = ["default".to_string()].into_iter().collect();
+
+        assert!(tick_missing_names(&seen, &absent, &mut missing_ticks).is_empty());
+        assert!(tick_missing_names(&seen, &present, &mut missing_ticks).is_empty());
+        assert!(
+            !missing_ticks.contains_key("default"),
+            "a reappearing device should clear its missing-tick counter"
+        );
+    }
+
+    #[test]
+    fn promotes_after_two_consecutive_present_polls() {
+        let mut present_ticks = HashMap::new();
+        let now: HashSet<String> = ["headphones".to_string()].into_iter().collect();
+
+        let debounced = tick_present_names(&now, &mut present_ticks);
+        assert!(
+            debounced.is_empty(),
+            "a single poll of presence should not yet trigger a switch"
+        );
+
+        let debounced = tick_present_names(&now, &mut present_ticks);
+        assert_eq!(
+            debounced,
+            vec!["headphones".to_string()],
+            "a device present for DEBOUNCE_TICKS consecutive polls should be eligible to switch to"
+        );
+    }
+
+    #[test]
+    fn resets_present_debounce_when_device_flaps() {
+        let mut present_ticks = HashMap::new();
+        let present: HashSet<String> = ["headphones".to_string()].into_iter().collect();
+        let absent: HashSet<String> = HashSet::new();
+
+        assert!(tick_present_names(&present, &mut present_ticks).is_empty());
+        assert!(tick_present_names(&absent, &mut present_ticks).is_empty());
+        assert!(
+            !present_ticks.contains_key("headphones"),
+            "a device that disappeared again should clear its presence counter"
+        );
+    }
+}